@@ -1,94 +1,222 @@
-use alloy_consensus::TxEnvelope;
+mod partial_tx;
+mod priority_queue;
+mod resilient;
+
+use alloy_consensus::{Transaction as _, TxEnvelope};
+use alloy_primitives::{Bytes, TxHash, B256};
 use alloy_provider::{Network, Provider};
 use alloy_rpc_types_eth::Transaction;
 use alloy_transport::TransportResult;
 use async_trait::async_trait;
-use serde::{Deserialize, Deserializer};
-use serde_json::Value;
+use serde::{Deserialize, Deserializer, Serialize};
 use tracing::error;
 
+pub use partial_tx::{
+    MevBlockerPartialEip1559Tx, MevBlockerPartialEip2930Tx, MevBlockerPartialEip4844Tx, MevBlockerPartialEip7702Tx, MevBlockerPartialLegacyTx,
+    MevBlockerPartialTx,
+};
+pub use priority_queue::{MevBlockerPriorityQueue, MevBlockerScoringFn, PendingIterator, UnorderedIterator};
+pub use resilient::{ResilientSubscription, ResilientSubscriptionConfig};
+
 pub const MEV_BLOCKER_SEARCHERS_URL: &str = "wss://searchers.mevblocker.io";
 
 #[derive(Debug, Clone)]
 pub struct MevBlockerTx(pub Transaction<TxEnvelope>);
 
-// Adjust fields to parse into `alloy_rpc_types_eth::Transaction`.
-// MEV Blocker pending transactions lacks e.g. fields like `r`, `s`, `v`, and `yParity`.
+// `MevBlockerPartialTx` models exactly the fields MEV Blocker sends. This impl is kept only for
+// backward compatibility with consumers already using `alloy_rpc_types_eth::Transaction`; it
+// fabricates a signature, which is meaningless for these unsigned partial transactions.
 // API doc: https://docs.cow.fi/mevblocker/searchers/bidding-on-transactions
 impl<'de> Deserialize<'de> for MevBlockerTx {
     fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
     where
         D: Deserializer<'de>,
     {
-        let mut value: Value = Deserialize::deserialize(deserializer)?;
-        let original_input = value.to_string(); // Save the original value for logging
-
-        // If the "type" field is missing, add type 0x0
-        if value.get("type").is_none()
-            && let Some(obj) = value.as_object_mut()
-        {
-            obj.insert("type".to_string(), Value::String("0x0".to_string()));
-        }
+        let partial = MevBlockerPartialTx::deserialize(deserializer)?;
+        let value = partial_tx::into_signed_transaction_json(&partial);
 
-        // Put the content of the "data" field into the "input" field
-        // If the "data" field is null use "0x" as the default value
-        if let Some(data) = value.get_mut("data") {
-            let mut input = data.take();
-            if input.is_null() {
-                input = Value::String("0x".to_string());
+        let tx: Transaction<TxEnvelope> = match serde_json::from_value(value) {
+            Ok(tx) => tx,
+            Err(err) => {
+                // This can only happen when the format of MEV Blocker changes, or we have a bug.
+                // Log this error here with the partial transaction, because it will be swallowed by Alloy.
+                error!(?err, ?partial, "Error deserializing MevBlockerTx");
+                return Err(serde::de::Error::custom(err));
             }
-            if let Some(obj) = value.as_object_mut() {
-                obj.insert("input".to_string(), input);
+        };
+
+        Ok(MevBlockerTx(tx))
+    }
+}
+
+impl MevBlockerTx {
+    /// Effective gas price this transaction pays per unit of gas, given the block's `base_fee`.
+    ///
+    /// For legacy transactions (type 0x0/0x1) this is simply `gasPrice`. For EIP-1559 and later
+    /// (type 0x2/0x3/0x4) it is `min(maxFeePerGas, base_fee + maxPriorityFeePerGas)`.
+    pub fn effective_gas_price(&self, base_fee: u128) -> u128 {
+        match self.0.gas_price() {
+            Some(gas_price) => gas_price,
+            None => {
+                let max_fee_per_gas = self.0.max_fee_per_gas();
+                let max_priority_fee_per_gas = self.0.max_priority_fee_per_gas().unwrap_or_default();
+                max_fee_per_gas.min(base_fee.saturating_add(max_priority_fee_per_gas))
             }
         }
-        value.as_object_mut().unwrap().remove("data");
+    }
 
-        if value.get("type").unwrap_or(&Value::String("0x".to_string())).as_str().unwrap_or_default() == "0x3" {
-            if value.get("blobVersionedHashes").is_none()
-                && let Some(obj) = value.as_object_mut()
-            {
-                obj.insert("blobVersionedHashes".to_string(), Value::Array(vec![]));
-            }
-            if value.get("maxFeePerBlobGas").is_none()
-                && let Some(obj) = value.as_object_mut()
-            {
-                obj.insert("maxFeePerBlobGas".to_string(), Value::String("0x0".to_string()));
+    /// Effective priority tip per unit of gas paid to the block proposer, given `base_fee`.
+    ///
+    /// Returns `0` once `base_fee` exceeds `maxFeePerGas`, since the transaction would currently
+    /// be unincludable. The blob fee of type 0x3 transactions is not folded in here; use
+    /// [`MevBlockerTx::blob_fee_per_gas`] for that.
+    pub fn effective_priority_tip(&self, base_fee: u128) -> u128 {
+        match self.0.gas_price() {
+            Some(gas_price) => gas_price.saturating_sub(base_fee),
+            None => {
+                let max_fee_per_gas = self.0.max_fee_per_gas();
+                if base_fee > max_fee_per_gas {
+                    return 0;
+                }
+                let max_priority_fee_per_gas = self.0.max_priority_fee_per_gas().unwrap_or_default();
+                self.effective_gas_price(base_fee).saturating_sub(base_fee).min(max_priority_fee_per_gas)
             }
         }
+    }
+
+    /// Blob fee per unit of blob gas this transaction is willing to pay, if it is a blob
+    /// transaction (type 0x3).
+    pub fn blob_fee_per_gas(&self) -> Option<u128> {
+        self.0.max_fee_per_blob_gas()
+    }
+}
+
+/// A bundle submitted to MEV Blocker in response to an observed partial pending transaction.
+///
+/// Backrunning searchers build this via [`MevBlockerBundle::new`] to place their own signed
+/// transaction(s) after the observed one in a target block, bidding back part of the profit.
+#[derive(Debug, Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MevBlockerBundle {
+    /// Hash of the observed `MevBlockerTx` this bundle backruns.
+    pub pending_transaction_hash: TxHash,
+    /// Signed raw transactions to include in the bundle, in order.
+    pub raw_transactions: Vec<Bytes>,
+    /// Target block number the bundle should land in.
+    #[serde(with = "alloy_serde::quantity")]
+    pub block_number: u64,
+    /// Percentage (0-100) of the searcher's profit bid back to MEV Blocker.
+    pub refund_percent: u64,
+}
 
-        // If the "type" field is 0x4 and "authorizationList" is missing, add an empty array
-        if value.get("type").unwrap_or(&Value::String("0x".to_string())).as_str().unwrap_or_default() == "0x4"
-            && value.get("authorizationList").is_none()
-            && let Some(obj) = value.as_object_mut()
-        {
-            obj.insert("authorizationList".to_string(), Value::Array(vec![]));
+impl MevBlockerBundle {
+    /// Starts building a bundle that backruns `pending_transaction_hash`.
+    pub fn new(pending_transaction_hash: TxHash) -> MevBlockerBundleBuilder {
+        MevBlockerBundleBuilder {
+            pending_transaction_hash,
+            raw_transactions: Vec::new(),
+            block_number: None,
+            refund_percent: None,
         }
+    }
+}
+
+/// Builder for [`MevBlockerBundle`].
+#[derive(Debug, Clone)]
+pub struct MevBlockerBundleBuilder {
+    pending_transaction_hash: TxHash,
+    raw_transactions: Vec<Bytes>,
+    block_number: Option<u64>,
+    refund_percent: Option<u64>,
+}
+
+impl MevBlockerBundleBuilder {
+    /// Appends a signed raw transaction to the bundle.
+    pub fn push_transaction(mut self, raw_signed_tx: impl Into<Bytes>) -> Self {
+        self.raw_transactions.push(raw_signed_tx.into());
+        self
+    }
+
+    /// Sets the target block number for the bundle.
+    pub fn block_number(mut self, block_number: u64) -> Self {
+        self.block_number = Some(block_number);
+        self
+    }
+
+    /// Sets the percentage (0-100) of profit bid back to MEV Blocker.
+    pub fn refund_percent(mut self, refund_percent: u64) -> Self {
+        self.refund_percent = Some(refund_percent);
+        self
+    }
 
-        // Add the "r", "s", "v" fields
-        if let Some(obj) = value.as_object_mut() {
-            obj.insert("r".to_string(), Value::String("".to_string()));
-            obj.insert("s".to_string(), Value::String("".to_string()));
-            obj.insert("v".to_string(), Value::String("0x1B".to_string()));
-            obj.insert("yParity".to_string(), Value::String("0x1".to_string()));
+    /// Builds the bundle.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if no raw transactions were added, `block_number`/`refund_percent` were
+    /// never set, or `refund_percent` is not between 0 and 100.
+    pub fn build(self) -> Result<MevBlockerBundle, MevBlockerBundleBuilderError> {
+        if self.raw_transactions.is_empty() {
+            return Err(MevBlockerBundleBuilderError::NoTransactions);
+        }
+        let block_number = self.block_number.ok_or(MevBlockerBundleBuilderError::MissingBlockNumber)?;
+        let refund_percent = self.refund_percent.ok_or(MevBlockerBundleBuilderError::MissingRefundPercent)?;
+        if refund_percent > 100 {
+            return Err(MevBlockerBundleBuilderError::RefundPercentOutOfRange(refund_percent));
         }
 
-        let tx: Transaction<TxEnvelope> = match serde_json::from_value(value) {
-            Ok(tx) => tx,
-            Err(err) => {
-                // This can only happen when the format of MEV Blocker changes, or we have a bug.
-                // Log this error here with the original input, because it will be swallowed by Alloy.
-                error!(?err, %original_input, "Error deserializing MevBlockerTx");
-                return Err(serde::de::Error::custom(err));
-            }
-        };
+        Ok(MevBlockerBundle { pending_transaction_hash: self.pending_transaction_hash, raw_transactions: self.raw_transactions, block_number, refund_percent })
+    }
+}
 
-        Ok(MevBlockerTx(tx))
+/// Error returned by [`MevBlockerBundleBuilder::build`] when the bundle is incomplete or invalid.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum MevBlockerBundleBuilderError {
+    /// No raw transactions were added to the bundle.
+    NoTransactions,
+    /// [`MevBlockerBundleBuilder::block_number`] was never called.
+    MissingBlockNumber,
+    /// [`MevBlockerBundleBuilder::refund_percent`] was never called.
+    MissingRefundPercent,
+    /// `refund_percent` must be between 0 and 100.
+    RefundPercentOutOfRange(u64),
+}
+
+impl std::fmt::Display for MevBlockerBundleBuilderError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::NoTransactions => write!(f, "MevBlockerBundle requires at least one raw transaction"),
+            Self::MissingBlockNumber => write!(f, "MevBlockerBundle requires a block number"),
+            Self::MissingRefundPercent => write!(f, "MevBlockerBundle requires a refund percent"),
+            Self::RefundPercentOutOfRange(value) => write!(f, "refund_percent must be between 0 and 100, got {value}"),
+        }
     }
 }
 
+impl std::error::Error for MevBlockerBundleBuilderError {}
+
+/// Response returned by MEV Blocker after accepting a submitted bundle.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MevBlockerBundleResponse {
+    /// Hash identifying the accepted bundle.
+    pub bundle_hash: B256,
+}
+
 #[async_trait]
 pub trait MevBlockerApi<N>: Send + Sync {
     async fn subscribe_mev_blocker_pending_transactions(&self) -> TransportResult<alloy_pubsub::Subscription<MevBlockerTx>>;
+
+    /// Submits a bundle backrunning an observed partial pending transaction.
+    async fn send_mev_blocker_bundle(&self, bundle: MevBlockerBundle) -> TransportResult<MevBlockerBundleResponse>;
+
+    /// Like [`subscribe_mev_blocker_pending_transactions`](Self::subscribe_mev_blocker_pending_transactions),
+    /// but automatically reconnects and resubscribes on transport error or stream termination,
+    /// with exponential backoff, instead of ending the stream. If `config.max_retries` is set and
+    /// that many consecutive retries fail, the stream ends.
+    async fn subscribe_mev_blocker_pending_transactions_resilient(&self, config: ResilientSubscriptionConfig) -> ResilientSubscription
+    where
+        Self: Clone + Send + Sync + 'static;
 }
 
 #[async_trait]
@@ -105,6 +233,17 @@ where
         let id = call.await?;
         self.root().get_subscription(id).await
     }
+
+    async fn send_mev_blocker_bundle(&self, bundle: MevBlockerBundle) -> TransportResult<MevBlockerBundleResponse> {
+        self.client().request("mevBlocker_sendBundle", (bundle,)).await
+    }
+
+    async fn subscribe_mev_blocker_pending_transactions_resilient(&self, config: ResilientSubscriptionConfig) -> ResilientSubscription
+    where
+        Self: Clone + Send + Sync + 'static,
+    {
+        ResilientSubscription::spawn::<N, Self>(self.clone(), config)
+    }
 }
 
 #[cfg(test)]
@@ -252,4 +391,119 @@ mod tests {
         assert_eq!(tx.0.from(), address!("a1b2c3d4e5f6789abcdef0123456789abcdef012"));
         assert_eq!(tx.0.tx_hash(), TxHash::from_str("0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef").unwrap());
     }
+
+    #[test]
+    fn test_effective_gas_price_legacy() {
+        let tx_raw = r#"{
+            "nonce": "0x1",
+            "gasPrice": "0x3b9aca00",
+            "gas": "0xb6bd",
+            "to": "0xa1b2c3d4e5f6789abcdef0123456789abcdef012",
+            "value": "0x0",
+            "data": "0x1234",
+            "hash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "from": "0xfedcba0987654321fedcba0987654321fedcba09"
+        }"#;
+        let tx: MevBlockerTx = serde_json::from_str(tx_raw).unwrap();
+
+        let base_fee = 0x2540be400u128; // 10 gwei
+        assert_eq!(tx.effective_gas_price(base_fee), 0x3b9aca00); // gasPrice, unaffected by base_fee
+        assert_eq!(tx.effective_priority_tip(base_fee), 0x3b9aca00u128.saturating_sub(base_fee));
+    }
+
+    #[test]
+    fn test_effective_gas_price_eip1559() {
+        let tx_raw = r#"{
+            "chainId": "0x1",
+            "to": "0x9876543210abcdef9876543210abcdef98765432",
+            "value": "0x0",
+            "data": "0x1234",
+            "accessList": [],
+            "nonce": "0xa",
+            "maxPriorityFeePerGas": "0x77359400",
+            "maxFeePerGas": "0x174876e800",
+            "gas": "0x262e6",
+            "type": "0x2",
+            "hash": "0x3333333333333333333333333333333333333333333333333333333333333333",
+            "from": "0xabcdef0123456789abcdef0123456789abcdef01"
+        }"#;
+        let tx: MevBlockerTx = serde_json::from_str(tx_raw).unwrap();
+
+        // base_fee low enough that max_fee_per_gas isn't the binding constraint.
+        let base_fee = 0x2540be400u128; // 10 gwei
+        assert_eq!(tx.effective_gas_price(base_fee), base_fee + 0x77359400);
+        assert_eq!(tx.effective_priority_tip(base_fee), 0x77359400);
+
+        // base_fee above maxFeePerGas: transaction is currently unincludable.
+        let prohibitive_base_fee = 0x174876e800u128 + 1;
+        assert_eq!(tx.effective_priority_tip(prohibitive_base_fee), 0);
+    }
+
+    #[test]
+    fn test_blob_fee_per_gas() {
+        let tx_raw = r#"{
+            "accessList": [],
+            "chainId": "0x1",
+            "data": null,
+            "from": "0x6789abcdef0123456789abcdef0123456789abcd",
+            "gas": "0x5208",
+            "hash": "0x5555555555555555555555555555555555555555555555555555555555555555",
+            "maxFeePerGas": "0x60b66031a",
+            "maxPriorityFeePerGas": "0x0",
+            "maxFeePerBlobGas": "0x3b9aca00",
+            "nonce": "0x6663",
+            "to": "0xcdef0123456789abcdef0123456789abcdef0123",
+            "type": "0x3",
+            "value": "0x0"
+        }"#;
+        let tx: MevBlockerTx = serde_json::from_str(tx_raw).unwrap();
+        assert_eq!(tx.blob_fee_per_gas(), Some(0x3b9aca00));
+    }
+
+    #[test]
+    fn test_bundle_builder_roundtrip() {
+        let pending_tx_hash = TxHash::from_str("0x1111111111111111111111111111111111111111111111111111111111111111").unwrap();
+        let bundle = MevBlockerBundle::new(pending_tx_hash)
+            .push_transaction(Bytes::from_str("0xdeadbeef").unwrap())
+            .push_transaction(Bytes::from_str("0xf00dbabe").unwrap())
+            .block_number(19_000_000)
+            .refund_percent(90)
+            .build()
+            .unwrap();
+
+        let json = serde_json::to_value(&bundle).unwrap();
+        assert_eq!(json["pendingTransactionHash"], serde_json::json!(pending_tx_hash));
+        assert_eq!(json["rawTransactions"], serde_json::json!(["0xdeadbeef", "0xf00dbabe"]));
+        assert_eq!(json["blockNumber"], serde_json::json!("0x121eac0"));
+        assert_eq!(json["refundPercent"], serde_json::json!(90));
+    }
+
+    #[test]
+    fn test_bundle_response_deserialize() {
+        let raw = r#"{"bundleHash":"0x6666666666666666666666666666666666666666666666666666666666666666"}"#;
+        let response: MevBlockerBundleResponse = serde_json::from_str(raw).unwrap();
+        assert_eq!(
+            response.bundle_hash,
+            B256::from_str("0x6666666666666666666666666666666666666666666666666666666666666666").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_bundle_builder_requires_transaction() {
+        let pending_tx_hash = TxHash::from_str("0x1111111111111111111111111111111111111111111111111111111111111111").unwrap();
+        let err = MevBlockerBundle::new(pending_tx_hash).block_number(1).refund_percent(50).build().unwrap_err();
+        assert_eq!(err, MevBlockerBundleBuilderError::NoTransactions);
+    }
+
+    #[test]
+    fn test_bundle_builder_rejects_out_of_range_refund_percent() {
+        let pending_tx_hash = TxHash::from_str("0x1111111111111111111111111111111111111111111111111111111111111111").unwrap();
+        let err = MevBlockerBundle::new(pending_tx_hash)
+            .push_transaction(Bytes::from_str("0xdeadbeef").unwrap())
+            .block_number(1)
+            .refund_percent(101)
+            .build()
+            .unwrap_err();
+        assert_eq!(err, MevBlockerBundleBuilderError::RefundPercentOutOfRange(101));
+    }
 }
@@ -0,0 +1,355 @@
+use alloy_eips::eip2930::AccessList;
+use alloy_eips::eip7702::SignedAuthorization;
+use alloy_primitives::{Address, Bytes, TxHash, B256, U256};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+
+/// MEV Blocker's own encoding of a partial (unsigned) pending transaction, exactly as received
+/// over the `mevBlocker_subscribePartialPendingTransactions` subscription.
+///
+/// Unlike [`crate::MevBlockerTx`], this type carries no signature fields at all — MEV Blocker
+/// never sends one for these partial transactions, so there is nothing faithful to decode into.
+/// Deserialization dispatches on the `type` field (defaulting to legacy when absent, per the
+/// API doc: <https://docs.cow.fi/mevblocker/searchers/bidding-on-transactions>) and fails loudly
+/// if it sees a transaction type this crate doesn't know about.
+#[derive(Debug, Clone)]
+pub enum MevBlockerPartialTx {
+    Legacy(MevBlockerPartialLegacyTx),
+    Eip2930(MevBlockerPartialEip2930Tx),
+    Eip1559(MevBlockerPartialEip1559Tx),
+    Eip4844(MevBlockerPartialEip4844Tx),
+    Eip7702(MevBlockerPartialEip7702Tx),
+}
+
+impl MevBlockerPartialTx {
+    /// EIP-2718 transaction type byte (`0x0`-`0x4`).
+    pub fn tx_type(&self) -> u8 {
+        match self {
+            Self::Legacy(_) => 0x0,
+            Self::Eip2930(_) => 0x1,
+            Self::Eip1559(_) => 0x2,
+            Self::Eip4844(_) => 0x3,
+            Self::Eip7702(_) => 0x4,
+        }
+    }
+
+    /// Hash MEV Blocker assigned to this pending transaction.
+    pub fn hash(&self) -> TxHash {
+        match self {
+            Self::Legacy(tx) => tx.hash,
+            Self::Eip2930(tx) => tx.hash,
+            Self::Eip1559(tx) => tx.hash,
+            Self::Eip4844(tx) => tx.hash,
+            Self::Eip7702(tx) => tx.hash,
+        }
+    }
+
+    /// Sender of this pending transaction.
+    pub fn from(&self) -> Address {
+        match self {
+            Self::Legacy(tx) => tx.from,
+            Self::Eip2930(tx) => tx.from,
+            Self::Eip1559(tx) => tx.from,
+            Self::Eip4844(tx) => tx.from,
+            Self::Eip7702(tx) => tx.from,
+        }
+    }
+
+    /// Sender's nonce for this pending transaction.
+    pub fn nonce(&self) -> u64 {
+        match self {
+            Self::Legacy(tx) => tx.nonce,
+            Self::Eip2930(tx) => tx.nonce,
+            Self::Eip1559(tx) => tx.nonce,
+            Self::Eip4844(tx) => tx.nonce,
+            Self::Eip7702(tx) => tx.nonce,
+        }
+    }
+}
+
+/// Unsigned legacy (type `0x0`) pending transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MevBlockerPartialLegacyTx {
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_price: u128,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas: u64,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Bytes,
+    pub from: Address,
+    pub hash: TxHash,
+}
+
+/// Unsigned EIP-2930 (type `0x1`) pending transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MevBlockerPartialEip2930Tx {
+    #[serde(with = "alloy_serde::quantity")]
+    pub chain_id: u64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas_price: u128,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas: u64,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Bytes,
+    pub access_list: AccessList,
+    pub from: Address,
+    pub hash: TxHash,
+}
+
+/// Unsigned EIP-1559 (type `0x2`) pending transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MevBlockerPartialEip1559Tx {
+    #[serde(with = "alloy_serde::quantity")]
+    pub chain_id: u64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub max_fee_per_gas: u128,
+    #[serde(with = "alloy_serde::quantity")]
+    pub max_priority_fee_per_gas: u128,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas: u64,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Bytes,
+    pub access_list: AccessList,
+    pub from: Address,
+    pub hash: TxHash,
+}
+
+/// Unsigned EIP-4844 (type `0x3`) pending transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MevBlockerPartialEip4844Tx {
+    #[serde(with = "alloy_serde::quantity")]
+    pub chain_id: u64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub max_fee_per_gas: u128,
+    #[serde(with = "alloy_serde::quantity")]
+    pub max_priority_fee_per_gas: u128,
+    #[serde(with = "alloy_serde::quantity", default)]
+    pub max_fee_per_blob_gas: u128,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas: u64,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Bytes,
+    pub access_list: AccessList,
+    #[serde(default)]
+    pub blob_versioned_hashes: Vec<B256>,
+    pub from: Address,
+    pub hash: TxHash,
+}
+
+/// Unsigned EIP-7702 (type `0x4`) pending transaction.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct MevBlockerPartialEip7702Tx {
+    #[serde(with = "alloy_serde::quantity")]
+    pub chain_id: u64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub nonce: u64,
+    #[serde(with = "alloy_serde::quantity")]
+    pub max_fee_per_gas: u128,
+    #[serde(with = "alloy_serde::quantity")]
+    pub max_priority_fee_per_gas: u128,
+    #[serde(with = "alloy_serde::quantity")]
+    pub gas: u64,
+    pub to: Option<Address>,
+    pub value: U256,
+    pub input: Bytes,
+    pub access_list: AccessList,
+    #[serde(default)]
+    pub authorization_list: Vec<SignedAuthorization>,
+    pub from: Address,
+    pub hash: TxHash,
+}
+
+impl<'de> Deserialize<'de> for MevBlockerPartialTx {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let mut value: Value = Deserialize::deserialize(deserializer)?;
+
+        // If the "type" field is missing, MEV Blocker means a legacy transaction.
+        if value.get("type").is_none()
+            && let Some(obj) = value.as_object_mut()
+        {
+            obj.insert("type".to_string(), Value::String("0x0".to_string()));
+        }
+
+        // Put the content of the "data" field into the "input" field.
+        // If the "data" field is null use "0x" as the default value.
+        if let Some(data) = value.get_mut("data") {
+            let mut input = data.take();
+            if input.is_null() {
+                input = Value::String("0x".to_string());
+            }
+            if let Some(obj) = value.as_object_mut() {
+                obj.insert("input".to_string(), input);
+            }
+        }
+        if let Some(obj) = value.as_object_mut() {
+            obj.remove("data");
+        }
+
+        let tx_type = value.get("type").and_then(Value::as_str).unwrap_or("0x0").to_string();
+
+        match tx_type.as_str() {
+            "0x0" => Ok(Self::Legacy(serde_json::from_value(value).map_err(serde::de::Error::custom)?)),
+            "0x1" => Ok(Self::Eip2930(serde_json::from_value(value).map_err(serde::de::Error::custom)?)),
+            "0x2" => Ok(Self::Eip1559(serde_json::from_value(value).map_err(serde::de::Error::custom)?)),
+            "0x3" => Ok(Self::Eip4844(serde_json::from_value(value).map_err(serde::de::Error::custom)?)),
+            "0x4" => Ok(Self::Eip7702(serde_json::from_value(value).map_err(serde::de::Error::custom)?)),
+            other => Err(serde::de::Error::custom(format!("unsupported MEV Blocker transaction type: {other}"))),
+        }
+    }
+}
+
+/// Builds the JSON value `MevBlockerTx`'s legacy `Deserialize` impl expects, by serializing the
+/// typed partial transaction back out and stamping in the synthetic signature fields MEV Blocker
+/// never sends. This keeps the fabrication in one place instead of smeared across untyped JSON.
+pub(crate) fn into_signed_transaction_json(partial: &MevBlockerPartialTx) -> Value {
+    let mut value = match partial {
+        MevBlockerPartialTx::Legacy(tx) => serde_json::to_value(tx),
+        MevBlockerPartialTx::Eip2930(tx) => serde_json::to_value(tx),
+        MevBlockerPartialTx::Eip1559(tx) => serde_json::to_value(tx),
+        MevBlockerPartialTx::Eip4844(tx) => serde_json::to_value(tx),
+        MevBlockerPartialTx::Eip7702(tx) => serde_json::to_value(tx),
+    }
+    .expect("typed partial transaction fields always serialize");
+
+    if let Some(obj) = value.as_object_mut() {
+        obj.insert("type".to_string(), Value::String(format!("{:#x}", partial.tx_type())));
+        obj.insert("r".to_string(), Value::String("".to_string()));
+        obj.insert("s".to_string(), Value::String("".to_string()));
+        obj.insert("v".to_string(), Value::String("0x1B".to_string()));
+        obj.insert("yParity".to_string(), Value::String("0x1".to_string()));
+    }
+
+    value
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_missing_type_decodes_as_legacy() {
+        let tx_raw = r#"{
+            "nonce": "0x1",
+            "gasPrice": "0x171a390d1",
+            "gas": "0xb6bd",
+            "to": "0xa1b2c3d4e5f6789abcdef0123456789abcdef012",
+            "value": "0x0",
+            "data": "0x1234",
+            "hash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "from": "0xfedcba0987654321fedcba0987654321fedcba09"
+        }"#;
+
+        let tx: MevBlockerPartialTx = serde_json::from_str(tx_raw).unwrap();
+        assert!(matches!(tx, MevBlockerPartialTx::Legacy(_)));
+        assert_eq!(tx.tx_type(), 0x0);
+    }
+
+    #[test]
+    fn test_data_aliases_to_input() {
+        let tx_raw = r#"{
+            "nonce": "0x1",
+            "gasPrice": "0x171a390d1",
+            "gas": "0xb6bd",
+            "to": "0xa1b2c3d4e5f6789abcdef0123456789abcdef012",
+            "value": "0x0",
+            "data": null,
+            "hash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "from": "0xfedcba0987654321fedcba0987654321fedcba09"
+        }"#;
+
+        let tx: MevBlockerPartialTx = serde_json::from_str(tx_raw).unwrap();
+        match tx {
+            MevBlockerPartialTx::Legacy(tx) => assert_eq!(tx.input, Bytes::from_static(b"")),
+            other => panic!("expected legacy, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_eip4844_defaults_applied() {
+        let tx_raw = r#"{
+            "accessList": [],
+            "chainId": "0x1",
+            "data": null,
+            "from": "0x6789abcdef0123456789abcdef0123456789abcd",
+            "gas": "0x5208",
+            "hash": "0x5555555555555555555555555555555555555555555555555555555555555555",
+            "maxFeePerGas": "0x60b66031a",
+            "maxPriorityFeePerGas": "0x0",
+            "nonce": "0x6663",
+            "to": "0xcdef0123456789abcdef0123456789abcdef0123",
+            "type": "0x3",
+            "value": "0x0"
+        }"#;
+
+        let tx: MevBlockerPartialTx = serde_json::from_str(tx_raw).unwrap();
+        match tx {
+            MevBlockerPartialTx::Eip4844(tx) => {
+                assert_eq!(tx.max_fee_per_blob_gas, 0);
+                assert!(tx.blob_versioned_hashes.is_empty());
+            }
+            other => panic!("expected eip4844, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_eip7702_defaults_applied() {
+        let tx_raw = r#"{
+            "accessList": [],
+            "chainId": "0x1",
+            "data": "0x1234",
+            "from": "0xa1b2c3d4e5f6789abcdef0123456789abcdef012",
+            "gas": "0x30d40",
+            "hash": "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef",
+            "maxFeePerGas": "0x1605dd319",
+            "maxPriorityFeePerGas": "0x0",
+            "nonce": "0x2c",
+            "to": "0xfedcba0987654321fedcba0987654321fedcba09",
+            "type": "0x4",
+            "value": "0x0"
+        }"#;
+
+        let tx: MevBlockerPartialTx = serde_json::from_str(tx_raw).unwrap();
+        match tx {
+            MevBlockerPartialTx::Eip7702(tx) => assert!(tx.authorization_list.is_empty()),
+            other => panic!("expected eip7702, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_unknown_type_fails_loudly() {
+        let tx_raw = r#"{
+            "nonce": "0x1",
+            "gasPrice": "0x1",
+            "gas": "0x1",
+            "to": "0xa1b2c3d4e5f6789abcdef0123456789abcdef012",
+            "value": "0x0",
+            "data": "0x",
+            "hash": "0x1111111111111111111111111111111111111111111111111111111111111111",
+            "from": "0xfedcba0987654321fedcba0987654321fedcba09",
+            "type": "0x5"
+        }"#;
+
+        let err = serde_json::from_str::<MevBlockerPartialTx>(tx_raw).unwrap_err();
+        assert!(err.to_string().contains("unsupported MEV Blocker transaction type"));
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+
+use alloy_consensus::Transaction as _;
+use alloy_primitives::Address;
+use alloy_provider::network::TransactionResponse as _;
+
+use crate::MevBlockerTx;
+
+/// Scores a transaction given a caller-supplied base fee, higher meaning more valuable to
+/// include first. The default scoring is [`MevBlockerTx::effective_priority_tip`].
+pub type MevBlockerScoringFn = Box<dyn Fn(&MevBlockerTx, u128) -> i128 + Send + Sync>;
+
+#[derive(Default)]
+struct SenderBucket {
+    /// The sender's current on-chain nonce, if known, set via
+    /// [`MevBlockerPriorityQueue::set_account_nonce`].
+    tracked_nonce: Option<u64>,
+    by_nonce: HashMap<u64, MevBlockerTx>,
+}
+
+/// A transaction-pool-style ordered view over MEV Blocker's pending transaction firehose.
+///
+/// Feed it transactions pulled off [`crate::MevBlockerApi::subscribe_mev_blocker_pending_transactions`]
+/// (or its resilient variant) via [`MevBlockerPriorityQueue::insert`], then read them back out
+/// ranked by [`MevBlockerPriorityQueue::pending`] (nonce-gap aware, highest score first) or
+/// [`MevBlockerPriorityQueue::unordered`] (raw arrival-order-agnostic view).
+pub struct MevBlockerPriorityQueue {
+    senders: HashMap<Address, SenderBucket>,
+    scoring: MevBlockerScoringFn,
+}
+
+impl Default for MevBlockerPriorityQueue {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MevBlockerPriorityQueue {
+    /// Creates an empty queue, scoring transactions by [`MevBlockerTx::effective_priority_tip`].
+    pub fn new() -> Self {
+        Self { senders: HashMap::new(), scoring: Box::new(|tx, base_fee| tx.effective_priority_tip(base_fee) as i128) }
+    }
+
+    /// Overrides how transactions are scored against each other, e.g. by gas, tip, or a custom
+    /// profit estimate.
+    pub fn set_scoring<F>(&mut self, scoring: F)
+    where
+        F: Fn(&MevBlockerTx, u128) -> i128 + Send + Sync + 'static,
+    {
+        self.scoring = Box::new(scoring);
+    }
+
+    /// Records `sender`'s current on-chain nonce, unblocking the lowest pending nonce for that
+    /// sender even if no earlier nonce was ever observed on the stream.
+    pub fn set_account_nonce(&mut self, sender: Address, nonce: u64) {
+        self.senders.entry(sender).or_default().tracked_nonce = Some(nonce);
+    }
+
+    /// Inserts a newly observed transaction, scored against `base_fee`.
+    ///
+    /// If a transaction for the same `(sender, nonce)` is already tracked, only the
+    /// higher-scored of the two is kept.
+    pub fn insert(&mut self, tx: MevBlockerTx, base_fee: u128) {
+        let sender = tx.0.from();
+        let nonce = tx.0.nonce();
+        let bucket = self.senders.entry(sender).or_default();
+
+        if let Some(existing) = bucket.by_nonce.get(&nonce) {
+            if (self.scoring)(&tx, base_fee) <= (self.scoring)(existing, base_fee) {
+                return;
+            }
+        }
+        bucket.by_nonce.insert(nonce, tx);
+    }
+
+    /// Iterates every tracked transaction that is ready to be included given what's been
+    /// observed so far, in descending score order against `base_fee`. Ties are broken
+    /// deterministically by ascending nonce, then by sender, so iteration order never depends on
+    /// `HashMap` iteration order.
+    ///
+    /// A transaction with nonce `N` from a given sender is ready if nonce `N - 1` from that
+    /// sender has also been observed, or the sender's tracked on-chain nonce (see
+    /// [`MevBlockerPriorityQueue::set_account_nonce`]) equals `N`. Nonce `0` is additionally
+    /// ready on its own whenever the sender has no tracked on-chain nonce yet, since `0` is
+    /// always a valid first nonce for an account — without this, the overwhelmingly common case
+    /// of a sender's very first observed transaction would never be ready until its nonce was
+    /// fetched out-of-band.
+    pub fn pending(&self, base_fee: u128) -> PendingIterator<'_> {
+        let mut ready: Vec<&MevBlockerTx> = self
+            .senders
+            .values()
+            .flat_map(|bucket| {
+                bucket.by_nonce.values().filter(move |tx| {
+                    let nonce = tx.0.nonce();
+                    let previous_nonce_observed = bucket.by_nonce.contains_key(&(nonce.wrapping_sub(1)));
+                    let tracked_nonce_matches = bucket.tracked_nonce == Some(nonce);
+                    let first_possible_nonce = nonce == 0 && bucket.tracked_nonce.is_none();
+                    previous_nonce_observed || tracked_nonce_matches || first_possible_nonce
+                })
+            })
+            .collect();
+        ready.sort_by_key(|tx| (std::cmp::Reverse((self.scoring)(tx, base_fee)), tx.0.nonce(), tx.0.from()));
+        PendingIterator { inner: ready.into_iter() }
+    }
+
+    /// Iterates every tracked transaction in unspecified order, regardless of nonce-gap
+    /// readiness or score.
+    pub fn unordered(&self) -> UnorderedIterator<'_> {
+        UnorderedIterator { inner: self.senders.values().flat_map(|bucket| bucket.by_nonce.values()).collect::<Vec<_>>().into_iter() }
+    }
+}
+
+/// Yields ready transactions in descending score order. See [`MevBlockerPriorityQueue::pending`].
+pub struct PendingIterator<'a> {
+    inner: std::vec::IntoIter<&'a MevBlockerTx>,
+}
+
+impl<'a> Iterator for PendingIterator<'a> {
+    type Item = &'a MevBlockerTx;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+/// Yields every tracked transaction, unranked. See [`MevBlockerPriorityQueue::unordered`].
+pub struct UnorderedIterator<'a> {
+    inner: std::vec::IntoIter<&'a MevBlockerTx>,
+}
+
+impl<'a> Iterator for UnorderedIterator<'a> {
+    type Item = &'a MevBlockerTx;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.inner.next()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy_primitives::address;
+
+    fn legacy_tx(nonce: &str, gas_price: &str, hash_byte: char) -> MevBlockerTx {
+        let hash = hash_byte.to_string().repeat(64);
+        let tx_raw = format!(
+            r#"{{
+                "nonce": "{nonce}",
+                "gasPrice": "{gas_price}",
+                "gas": "0xb6bd",
+                "to": "0xa1b2c3d4e5f6789abcdef0123456789abcdef012",
+                "value": "0x0",
+                "data": "0x1234",
+                "hash": "0x{hash}",
+                "from": "0xfedcba0987654321fedcba0987654321fedcba09"
+            }}"#
+        );
+        serde_json::from_str(&tx_raw).unwrap()
+    }
+
+    #[test]
+    fn test_pending_respects_nonce_gap() {
+        let mut queue = MevBlockerPriorityQueue::new();
+        queue.insert(legacy_tx("0x1", "0x3b9aca00", '1'), 0);
+        queue.insert(legacy_tx("0x3", "0x3b9aca00", '3'), 0); // gap at nonce 2
+
+        assert_eq!(queue.pending(0).count(), 0, "nonce 1 isn't ready with no tracked nonce, nonce 3 has a gap");
+
+        queue.set_account_nonce(address!("fedcba0987654321fedcba0987654321fedcba09"), 1);
+        let ready: Vec<_> = queue.pending(0).map(|tx| tx.0.nonce()).collect();
+        assert_eq!(ready, vec![1], "nonce 3 still gapped on nonce 2");
+
+        queue.insert(legacy_tx("0x2", "0x3b9aca00", '2'), 0);
+        let ready: Vec<_> = queue.pending(0).map(|tx| tx.0.nonce()).collect();
+        assert_eq!(ready, vec![1, 2, 3]);
+    }
+
+    #[test]
+    fn test_pending_nonce_zero_ready_without_tracked_nonce() {
+        let mut queue = MevBlockerPriorityQueue::new();
+        queue.insert(legacy_tx("0x0", "0x3b9aca00", '1'), 0);
+
+        let ready: Vec<_> = queue.pending(0).map(|tx| tx.0.nonce()).collect();
+        assert_eq!(ready, vec![0], "a sender's first-ever nonce must be ready without an out-of-band account nonce lookup");
+    }
+
+    #[test]
+    fn test_pending_orders_by_score_descending() {
+        let mut queue = MevBlockerPriorityQueue::new();
+        let sender = address!("fedcba0987654321fedcba0987654321fedcba09");
+        queue.set_account_nonce(sender, 0);
+        queue.insert(legacy_tx("0x0", "0x3b9aca00", '1'), 0); // 1 gwei
+        queue.insert(legacy_tx("0x1", "0x77359400", '2'), 0); // 2 gwei
+
+        let ready: Vec<_> = queue.pending(0).map(|tx| tx.0.nonce()).collect();
+        assert_eq!(ready, vec![1, 0], "higher gas price (nonce 1) should come first");
+    }
+
+    #[test]
+    fn test_higher_scored_duplicate_wins() {
+        let mut queue = MevBlockerPriorityQueue::new();
+        queue.insert(legacy_tx("0x0", "0x3b9aca00", '1'), 0); // 1 gwei
+        queue.insert(legacy_tx("0x0", "0x1dcd6500", '2'), 0); // 0.5 gwei, should be dropped
+
+        let txs: Vec<_> = queue.unordered().collect();
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].0.gas_price(), Some(0x3b9aca00));
+
+        queue.insert(legacy_tx("0x0", "0x77359400", '3'), 0); // 2 gwei, should replace
+
+        let txs: Vec<_> = queue.unordered().collect();
+        assert_eq!(txs.len(), 1);
+        assert_eq!(txs[0].0.gas_price(), Some(0x77359400));
+    }
+
+    #[test]
+    fn test_custom_scoring() {
+        let mut queue = MevBlockerPriorityQueue::new();
+        queue.set_scoring(|tx, _base_fee| -(tx.0.nonce() as i128)); // prefer lower nonces
+        let sender = address!("fedcba0987654321fedcba0987654321fedcba09");
+        queue.set_account_nonce(sender, 0);
+        queue.insert(legacy_tx("0x0", "0x1", '1'), 0);
+        queue.insert(legacy_tx("0x1", "0x1", '2'), 0);
+
+        let ready: Vec<_> = queue.pending(0).map(|tx| tx.0.nonce()).collect();
+        assert_eq!(ready, vec![0, 1]);
+    }
+
+    #[test]
+    fn test_unordered_returns_everything() {
+        let mut queue = MevBlockerPriorityQueue::new();
+        queue.insert(legacy_tx("0x5", "0x1", '1'), 0); // gapped, never "ready"
+        assert_eq!(queue.pending(0).count(), 0);
+        assert_eq!(queue.unordered().count(), 1);
+    }
+}
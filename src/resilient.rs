@@ -0,0 +1,271 @@
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+use alloy_provider::{Network, Provider};
+use alloy_transport::TransportResult;
+use async_trait::async_trait;
+use futures_util::{Stream, StreamExt};
+use rand::Rng;
+use tokio::sync::mpsc;
+use tracing::{error, warn};
+
+use crate::{MevBlockerApi, MevBlockerTx};
+
+/// Backoff parameters for [`ResilientSubscription`].
+///
+/// Backoff is exponential with full jitter: each retry sleeps a random duration between zero and
+/// `min(max_backoff, initial_backoff * 2^attempt)`.
+#[derive(Debug, Clone)]
+pub struct ResilientSubscriptionConfig {
+    /// Backoff applied after the first failed (re)connect attempt.
+    pub initial_backoff: Duration,
+    /// Upper bound the exponential backoff is capped at.
+    pub max_backoff: Duration,
+    /// Maximum number of consecutive retries (connect attempts after a failed connect or an ended
+    /// stream) before giving up, or `None` to retry forever.
+    pub max_retries: Option<u32>,
+}
+
+impl Default for ResilientSubscriptionConfig {
+    fn default() -> Self {
+        Self { initial_backoff: Duration::from_millis(500), max_backoff: Duration::from_secs(30), max_retries: None }
+    }
+}
+
+/// An auto-reconnecting view over `mevBlocker_subscribePartialPendingTransactions`.
+///
+/// On transport error or stream termination, the underlying subscription is re-established with
+/// exponential backoff and jitter, and items keep flowing to the same `Stream`. Dropping this
+/// value stops the background reconnect task.
+pub struct ResilientSubscription {
+    receiver: mpsc::UnboundedReceiver<MevBlockerTx>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl ResilientSubscription {
+    pub(crate) fn spawn<N, P>(provider: P, config: ResilientSubscriptionConfig) -> Self
+    where
+        N: Network,
+        P: Provider<N> + Clone + Send + Sync + 'static,
+    {
+        let (sender, receiver) = mpsc::unbounded_channel();
+        let task = tokio::spawn(run(ProviderSource::<N, P>::new(provider), config, sender));
+        Self { receiver, task }
+    }
+}
+
+impl Drop for ResilientSubscription {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+impl Stream for ResilientSubscription {
+    type Item = MevBlockerTx;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        self.receiver.poll_recv(cx)
+    }
+}
+
+/// A (re)connectable source of MEV Blocker pending transactions.
+///
+/// This indirection over [`MevBlockerApi`] exists so [`run`] can be exercised in tests against a
+/// fake source that simulates disconnects, without standing up a real `Provider`.
+#[async_trait]
+trait PendingTransactionSource: Send + Sync {
+    async fn connect(&self) -> TransportResult<Pin<Box<dyn Stream<Item = MevBlockerTx> + Send>>>;
+}
+
+struct ProviderSource<N, P> {
+    provider: P,
+    _network: std::marker::PhantomData<fn() -> N>,
+}
+
+impl<N, P> ProviderSource<N, P> {
+    fn new(provider: P) -> Self {
+        Self { provider, _network: std::marker::PhantomData }
+    }
+}
+
+#[async_trait]
+impl<N, P> PendingTransactionSource for ProviderSource<N, P>
+where
+    N: Network,
+    P: Provider<N> + Send + Sync,
+{
+    async fn connect(&self) -> TransportResult<Pin<Box<dyn Stream<Item = MevBlockerTx> + Send>>> {
+        let subscription = self.provider.subscribe_mev_blocker_pending_transactions().await?;
+        Ok(Box::pin(subscription.into_stream()))
+    }
+}
+
+async fn run<S>(source: S, config: ResilientSubscriptionConfig, sender: mpsc::UnboundedSender<MevBlockerTx>)
+where
+    S: PendingTransactionSource,
+{
+    let mut attempt: u32 = 0;
+
+    loop {
+        match source.connect().await {
+            Ok(mut stream) => {
+                attempt = 0;
+                loop {
+                    match stream.next().await {
+                        Some(tx) => {
+                            if sender.send(tx).is_err() {
+                                // Consumer dropped the `ResilientSubscription`.
+                                return;
+                            }
+                        }
+                        None => break,
+                    }
+                }
+                warn!("MEV Blocker pending transaction subscription ended, reconnecting");
+            }
+            Err(err) => {
+                warn!(?err, attempt, "failed to subscribe to MEV Blocker pending transactions");
+            }
+        }
+
+        attempt += 1;
+        if let Some(max_retries) = config.max_retries
+            && attempt > max_retries
+        {
+            error!(attempt, "exhausted retries subscribing to MEV Blocker pending transactions, giving up");
+            return;
+        }
+
+        // `attempt` counts retries starting at 1, but the backoff schedule itself is 0-indexed
+        // (the first retry waits `initial_backoff * 2^0`), hence `attempt - 1` here.
+        let backoff = backoff_with_jitter(&config, attempt - 1);
+        warn!(?backoff, attempt, "reconnecting to MEV Blocker pending transaction subscription");
+        tokio::time::sleep(backoff).await;
+    }
+}
+
+fn backoff_with_jitter(config: &ResilientSubscriptionConfig, attempt: u32) -> Duration {
+    rand::rng().random_range(Duration::ZERO..=backoff_ceiling(config, attempt))
+}
+
+fn backoff_ceiling(config: &ResilientSubscriptionConfig, attempt: u32) -> Duration {
+    config.initial_backoff.saturating_mul(1u32 << attempt.min(31)).min(config.max_backoff)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    #[test]
+    fn test_backoff_caps_at_max() {
+        let config = ResilientSubscriptionConfig { initial_backoff: Duration::from_millis(500), max_backoff: Duration::from_secs(5), max_retries: None };
+
+        for attempt in 0..20 {
+            let backoff = backoff_with_jitter(&config, attempt);
+            assert!(backoff <= config.max_backoff);
+        }
+    }
+
+    #[test]
+    fn test_backoff_grows_with_attempt() {
+        let config = ResilientSubscriptionConfig { initial_backoff: Duration::from_millis(100), max_backoff: Duration::from_secs(60), max_retries: None };
+
+        // The ceiling for a later attempt must be at least that of an earlier one.
+        let early_ceiling = backoff_ceiling(&config, 1);
+        let later_ceiling = backoff_ceiling(&config, 5);
+        assert!(later_ceiling >= early_ceiling);
+    }
+
+    fn legacy_tx(nonce: &str, hash_byte: char) -> MevBlockerTx {
+        let hash = hash_byte.to_string().repeat(64);
+        let tx_raw = format!(
+            r#"{{
+                "nonce": "{nonce}",
+                "gasPrice": "0x3b9aca00",
+                "gas": "0xb6bd",
+                "to": "0xa1b2c3d4e5f6789abcdef0123456789abcdef012",
+                "value": "0x0",
+                "data": "0x1234",
+                "hash": "0x{hash}",
+                "from": "0xfedcba0987654321fedcba0987654321fedcba09"
+            }}"#
+        );
+        serde_json::from_str(&tx_raw).unwrap()
+    }
+
+    /// Disconnects after the first item, then stays connected and yields a second item forever.
+    struct FlakyOnceSource {
+        connect_count: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl PendingTransactionSource for FlakyOnceSource {
+        async fn connect(&self) -> TransportResult<Pin<Box<dyn Stream<Item = MevBlockerTx> + Send>>> {
+            let attempt = self.connect_count.fetch_add(1, Ordering::SeqCst);
+            if attempt == 0 {
+                Ok(Box::pin(futures_util::stream::iter(vec![legacy_tx("0x1", '1')])))
+            } else {
+                Ok(Box::pin(futures_util::stream::iter(vec![legacy_tx("0x2", '2')]).chain(futures_util::stream::pending())))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_reconnects_after_stream_ends() {
+        use alloy_consensus::Transaction as _;
+
+        let connect_count = Arc::new(AtomicUsize::new(0));
+        let source = FlakyOnceSource { connect_count: connect_count.clone() };
+        let config = ResilientSubscriptionConfig { initial_backoff: Duration::from_millis(1), max_backoff: Duration::from_millis(5), max_retries: None };
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+
+        let task = tokio::spawn(run(source, config, sender));
+
+        let first = tokio::time::timeout(Duration::from_secs(1), receiver.recv()).await.unwrap().unwrap();
+        assert_eq!(first.0.nonce(), 1);
+
+        // The first source's stream ended after one item; `run` must reconnect on its own.
+        let second = tokio::time::timeout(Duration::from_secs(1), receiver.recv()).await.unwrap().unwrap();
+        assert_eq!(second.0.nonce(), 2);
+
+        assert_eq!(connect_count.load(Ordering::SeqCst), 2, "should have reconnected once after the first stream ended");
+        task.abort();
+    }
+
+    /// Fails to connect once, then succeeds.
+    struct FailOnceThenSucceedSource {
+        connect_count: Arc<AtomicUsize>,
+    }
+
+    #[async_trait]
+    impl PendingTransactionSource for FailOnceThenSucceedSource {
+        async fn connect(&self) -> TransportResult<Pin<Box<dyn Stream<Item = MevBlockerTx> + Send>>> {
+            let attempt = self.connect_count.fetch_add(1, Ordering::SeqCst);
+            if attempt == 0 {
+                Err(alloy_transport::TransportErrorKind::custom_str("simulated transport failure"))
+            } else {
+                Ok(Box::pin(futures_util::stream::iter(vec![legacy_tx("0x1", '1')]).chain(futures_util::stream::pending())))
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_run_retries_after_connect_error() {
+        use alloy_consensus::Transaction as _;
+
+        let connect_count = Arc::new(AtomicUsize::new(0));
+        let source = FailOnceThenSucceedSource { connect_count: connect_count.clone() };
+        let config = ResilientSubscriptionConfig { initial_backoff: Duration::from_millis(1), max_backoff: Duration::from_millis(5), max_retries: None };
+        let (sender, mut receiver) = mpsc::unbounded_channel();
+
+        let task = tokio::spawn(run(source, config, sender));
+
+        let tx = tokio::time::timeout(Duration::from_secs(1), receiver.recv()).await.unwrap().unwrap();
+        assert_eq!(tx.0.nonce(), 1);
+        assert_eq!(connect_count.load(Ordering::SeqCst), 2, "should have retried once after the failed connect");
+        task.abort();
+    }
+}